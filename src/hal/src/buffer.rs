@@ -2,8 +2,16 @@
 
 use std::error::Error;
 use std::fmt;
+use std::marker::PhantomData;
+use std::mem;
+use std::ops::Range;
 
+use smallvec::SmallVec;
+
+use format::Format;
 use memory;
+use pso::PipelineStage;
+use queue::QueueFamilyId;
 use {IndexType, Backend};
 
 /// Error creating a buffer.
@@ -18,10 +26,12 @@ pub enum CreationError {
 /// Error creating a `BufferView`.
 #[derive(Clone, Debug, PartialEq)]
 pub enum ViewError {
-    /// The required usage flag is not present in the image.
+    /// The required usage flag is not present in the buffer.
     Usage(Usage),
     /// The backend refused for some reason.
     Unsupported,
+    /// The offset does not respect the device's minimum texel buffer offset alignment.
+    Alignment(u64),
 }
 
 impl fmt::Display for ViewError {
@@ -29,7 +39,8 @@ impl fmt::Display for ViewError {
         let description = self.description();
         match *self {
             ViewError::Usage(usage) => write!(f, "{}: {:?}", description, usage),
-            _ => write!(f, "{}", description)
+            ViewError::Alignment(offset) => write!(f, "{}: {}", description, offset),
+            ViewError::Unsupported => write!(f, "{}", description),
         }
     }
 }
@@ -38,9 +49,11 @@ impl Error for ViewError {
     fn description(&self) -> &str {
         match *self {
             ViewError::Usage(_) =>
-                "The required usage flag is not present in the image",
+                "The required usage flag is not present in the buffer",
             ViewError::Unsupported =>
                 "The backend refused for some reason",
+            ViewError::Alignment(_) =>
+                "The offset does not respect the device's minimum texel buffer offset alignment",
         }
     }
 }
@@ -155,6 +168,167 @@ pub const MEMORY_WRITE: Access              = Access::MEMORY_WRITE;
 /// Buffer state
 pub type State = Access;
 
+/// A concrete, named way a buffer is used at some point in a command
+/// stream.
+///
+/// Each variant maps statically to the pipeline stage(s) it runs in and the
+/// [`Access`] flags it implies, so [`buffer_barrier`] can derive a barrier
+/// from a plain list of intended uses.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum AccessType {
+    /// Read as an index buffer.
+    IndexBuffer,
+    /// Read as a vertex buffer.
+    VertexBuffer,
+    /// Read as the argument buffer of an indirect draw/dispatch.
+    IndirectBuffer,
+    /// Read as a uniform buffer by a vertex shader.
+    VertexShaderReadUniformBuffer,
+    /// Read as a uniform buffer by a fragment shader.
+    FragmentShaderReadUniformBuffer,
+    /// Read as a uniform buffer by a compute shader.
+    ComputeShaderReadUniformBuffer,
+    /// Read as a storage buffer, or other non-uniform read, by a compute shader.
+    ComputeShaderReadOther,
+    /// Written as a storage buffer by a compute shader.
+    ComputeShaderWrite,
+    /// Source of a transfer (copy) command.
+    TransferRead,
+    /// Destination of a transfer (copy or fill) command.
+    TransferWrite,
+    /// Read by the host through mapped memory.
+    HostRead,
+    /// Written by the host through mapped memory.
+    HostWrite,
+}
+
+impl AccessType {
+    /// The pipeline stage(s) this access occurs in.
+    pub fn stage(&self) -> PipelineStage {
+        match *self {
+            AccessType::IndexBuffer |
+            AccessType::VertexBuffer => PipelineStage::VERTEX_INPUT,
+            AccessType::IndirectBuffer => PipelineStage::DRAW_INDIRECT,
+            AccessType::VertexShaderReadUniformBuffer => PipelineStage::VERTEX_SHADER,
+            AccessType::FragmentShaderReadUniformBuffer => PipelineStage::FRAGMENT_SHADER,
+            AccessType::ComputeShaderReadUniformBuffer |
+            AccessType::ComputeShaderReadOther |
+            AccessType::ComputeShaderWrite => PipelineStage::COMPUTE_SHADER,
+            AccessType::TransferRead |
+            AccessType::TransferWrite => PipelineStage::TRANSFER,
+            AccessType::HostRead |
+            AccessType::HostWrite => PipelineStage::HOST,
+        }
+    }
+
+    /// The `Access` flags this usage implies.
+    pub fn access(&self) -> Access {
+        match *self {
+            AccessType::IndexBuffer => Access::INDEX_BUFFER_READ,
+            AccessType::VertexBuffer => Access::VERTEX_BUFFER_READ,
+            AccessType::IndirectBuffer => Access::INDIRECT_COMMAND_READ,
+            AccessType::VertexShaderReadUniformBuffer |
+            AccessType::FragmentShaderReadUniformBuffer |
+            AccessType::ComputeShaderReadUniformBuffer => Access::CONSTANT_BUFFER_READ,
+            AccessType::ComputeShaderReadOther => Access::SHADER_READ,
+            AccessType::ComputeShaderWrite => Access::SHADER_WRITE,
+            AccessType::TransferRead => Access::TRANSFER_READ,
+            AccessType::TransferWrite => Access::TRANSFER_WRITE,
+            AccessType::HostRead => Access::HOST_READ,
+            AccessType::HostWrite => Access::HOST_WRITE,
+        }
+    }
+
+    /// Whether this usage writes to the buffer.
+    pub fn is_write(&self) -> bool {
+        match *self {
+            AccessType::ComputeShaderWrite |
+            AccessType::TransferWrite |
+            AccessType::HostWrite => true,
+            _ => false,
+        }
+    }
+}
+
+/// Derive the barrier needed to transition a buffer from the set of uses in
+/// `prev` to the set of uses in `next`.
+///
+/// The returned source/destination stage masks are the union of all
+/// `prev`/`next` stages. When none of `prev` is a write, the transition is
+/// read-to-read and needs no memory to be made visible, so the returned
+/// access masks are left empty (an execution-only barrier); otherwise the
+/// source mask covers the prior writes and the destination mask covers all
+/// of `next`.
+pub fn buffer_barrier(
+    prev: &[AccessType],
+    next: &[AccessType],
+) -> (PipelineStage, PipelineStage, State, State) {
+    let src_stage = prev.iter().fold(PipelineStage::empty(), |m, a| m | a.stage());
+    let dst_stage = next.iter().fold(PipelineStage::empty(), |m, a| m | a.stage());
+
+    if !prev.iter().any(AccessType::is_write) {
+        return (src_stage, dst_stage, State::empty(), State::empty());
+    }
+
+    let src_access = prev.iter()
+        .filter(|a| a.is_write())
+        .fold(State::empty(), |m, a| m | a.access());
+    let dst_access = next.iter().fold(State::empty(), |m, a| m | a.access());
+
+    (src_stage, dst_stage, src_access, dst_access)
+}
+
+#[cfg(test)]
+mod buffer_barrier_tests {
+    use super::*;
+
+    #[test]
+    fn read_to_read_omits_access_masks() {
+        let (src_stage, dst_stage, src_access, dst_access) = buffer_barrier(
+            &[AccessType::VertexBuffer],
+            &[AccessType::IndexBuffer],
+        );
+        assert_eq!(src_stage, PipelineStage::VERTEX_INPUT);
+        assert_eq!(dst_stage, PipelineStage::VERTEX_INPUT);
+        assert_eq!(src_access, State::empty());
+        assert_eq!(dst_access, State::empty());
+    }
+
+    #[test]
+    fn write_to_read_carries_the_prior_write_and_all_of_next() {
+        let (src_stage, dst_stage, src_access, dst_access) = buffer_barrier(
+            &[AccessType::TransferWrite],
+            &[AccessType::VertexBuffer, AccessType::IndexBuffer],
+        );
+        assert_eq!(src_stage, PipelineStage::TRANSFER);
+        assert_eq!(dst_stage, PipelineStage::VERTEX_INPUT);
+        assert_eq!(src_access, Access::TRANSFER_WRITE);
+        assert_eq!(
+            dst_access,
+            Access::VERTEX_BUFFER_READ | Access::INDEX_BUFFER_READ
+        );
+    }
+
+    #[test]
+    fn write_to_read_ignores_non_write_entries_of_prev() {
+        let (_, _, src_access, _) = buffer_barrier(
+            &[AccessType::VertexBuffer, AccessType::TransferWrite],
+            &[AccessType::IndexBuffer],
+        );
+        assert_eq!(src_access, Access::TRANSFER_WRITE);
+    }
+
+    #[test]
+    fn stage_masks_union_all_entries() {
+        let (src_stage, dst_stage, ..) = buffer_barrier(
+            &[AccessType::VertexBuffer, AccessType::TransferWrite],
+            &[AccessType::HostRead],
+        );
+        assert_eq!(src_stage, PipelineStage::VERTEX_INPUT | PipelineStage::TRANSFER);
+        assert_eq!(dst_stage, PipelineStage::HOST);
+    }
+}
+
 /// Index buffer view for `bind_index_buffer`.
 pub struct IndexBufferView<'a, B: Backend> {
     ///
@@ -165,6 +339,161 @@ pub struct IndexBufferView<'a, B: Backend> {
     pub index_type: IndexType,
 }
 
+/// Error produced when a [`BufferSlice`] would not fit within its parent,
+/// or would start at an offset that breaks the buffer's required alignment.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum SliceError {
+    /// The requested range falls outside the parent buffer or slice.
+    OutOfBounds,
+    /// The requested range's byte offset does not respect the buffer's
+    /// required alignment.
+    Misaligned,
+}
+
+impl fmt::Display for SliceError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.description())
+    }
+}
+
+impl Error for SliceError {
+    fn description(&self) -> &str {
+        match *self {
+            SliceError::OutOfBounds => "The requested range falls outside the parent buffer or slice",
+            SliceError::Misaligned => "The requested range's offset does not respect the buffer's required alignment",
+        }
+    }
+}
+
+/// Validate a sub-slice covering element `range` out of a parent of `len`
+/// elements, checking the resulting byte `offset` against `alignment` (as
+/// reported by [`complete_requirements`] for the backing buffer). Returns
+/// the new slice's size in bytes.
+fn validate_slice(
+    len: u64,
+    range: &Range<u64>,
+    elem_size: u64,
+    offset: u64,
+    alignment: u64,
+) -> Result<u64, SliceError> {
+    if range.start > range.end || range.end > len {
+        return Err(SliceError::OutOfBounds);
+    }
+    if offset % alignment != 0 {
+        return Err(SliceError::Misaligned);
+    }
+    Ok((range.end - range.start) * elem_size)
+}
+
+/// A typed, sub-sliceable view into a `B::Buffer`.
+///
+/// Carrying the element type `T` lets byte/element conversions, further
+/// sub-slicing, and producing an [`IndexBufferView`] all be derived safely
+/// instead of built up by hand from raw offsets.
+pub struct BufferSlice<'a, B: Backend, T = u8> {
+    buffer: &'a B::Buffer,
+    offset: u64,
+    size: u64,
+    alignment: u64,
+    _marker: PhantomData<T>,
+}
+
+impl<'a, B: Backend, T> BufferSlice<'a, B, T> {
+    /// Wrap the whole of `buffer`, which is `size` bytes long, checking
+    /// sub-slice offsets against `alignment` (the device's required
+    /// offset alignment for this buffer's usage, from
+    /// [`complete_requirements`]).
+    pub fn new(buffer: &'a B::Buffer, size: u64, alignment: u64) -> Self {
+        assert_ne!(mem::size_of::<T>(), 0, "BufferSlice does not support zero-sized types");
+        BufferSlice { buffer, offset: 0, size, alignment, _marker: PhantomData }
+    }
+
+    /// Number of `T` elements this slice covers.
+    pub fn len(&self) -> u64 {
+        self.size / mem::size_of::<T>() as u64
+    }
+
+    /// Byte offset of this slice within the root buffer.
+    pub fn offset(&self) -> u64 {
+        self.offset
+    }
+
+    /// Size of this slice in bytes.
+    pub fn size(&self) -> u64 {
+        self.size
+    }
+
+    /// Take a sub-slice covering element `range`, validating that it stays
+    /// within this slice and that its offset respects the buffer's
+    /// required alignment.
+    pub fn slice(&self, range: Range<u64>) -> Result<Self, SliceError> {
+        let elem_size = mem::size_of::<T>() as u64;
+        let offset = self.offset + range.start * elem_size;
+        let size = validate_slice(self.len(), &range, elem_size, offset, self.alignment)?;
+
+        Ok(BufferSlice {
+            buffer: self.buffer,
+            offset,
+            size,
+            alignment: self.alignment,
+            _marker: PhantomData,
+        })
+    }
+
+    /// Take the single element at `index`.
+    pub fn index(&self, index: u64) -> Result<Self, SliceError> {
+        self.slice(index..index + 1)
+    }
+
+    /// Reinterpret this slice as raw bytes, keeping its offset and size.
+    pub fn into_bytes(self) -> BufferSlice<'a, B, u8> {
+        BufferSlice {
+            buffer: self.buffer,
+            offset: self.offset,
+            size: self.size,
+            alignment: self.alignment,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Produce an [`IndexBufferView`] over this slice, so an index buffer
+    /// can be bound directly from a typed sub-allocation rather than a
+    /// hand-built view.
+    pub fn into_index_view(self, index_type: IndexType) -> IndexBufferView<'a, B> {
+        IndexBufferView { buffer: self.buffer, offset: self.offset, index_type }
+    }
+}
+
+#[cfg(test)]
+mod buffer_slice_tests {
+    use super::*;
+
+    #[test]
+    fn validate_slice_accepts_the_full_range() {
+        assert_eq!(validate_slice(4, &(0..4), 4, 0, 4), Ok(16));
+    }
+
+    #[test]
+    fn validate_slice_rejects_an_inverted_range() {
+        assert_eq!(validate_slice(4, &(3..1), 4, 12, 4), Err(SliceError::OutOfBounds));
+    }
+
+    #[test]
+    fn validate_slice_rejects_a_range_past_the_end() {
+        assert_eq!(validate_slice(4, &(0..5), 4, 0, 4), Err(SliceError::OutOfBounds));
+    }
+
+    #[test]
+    fn validate_slice_accepts_an_aligned_offset() {
+        assert_eq!(validate_slice(4, &(1..2), 4, 256, 256), Ok(4));
+    }
+
+    #[test]
+    fn validate_slice_rejects_a_misaligned_offset() {
+        assert_eq!(validate_slice(4, &(1..2), 4, 4, 256), Err(SliceError::Misaligned));
+    }
+}
+
 /// Retrieve the complete memory requirements for this buffer,
 /// taking usage and device limits into account
 pub fn complete_requirements<B: Backend>(
@@ -184,3 +513,413 @@ pub fn complete_requirements<B: Backend>(
     }
     requirements
 }
+
+/// A view of a sub-range of a buffer, bound to a texel `Format`.
+pub struct BufferView<'a, B: Backend> {
+    ///
+    pub buffer: &'a B::Buffer,
+    ///
+    pub format: Format,
+    ///
+    pub offset: u64,
+    ///
+    pub range: Option<u64>,
+}
+
+/// Check whether `usage` permits a texel buffer view, independent of the
+/// device and format (see [`create_buffer_view`]).
+fn validate_buffer_view_usage(usage: Usage) -> Result<(), ViewError> {
+    if !usage.intersects(Usage::UNIFORM_TEXEL | Usage::STORAGE_TEXEL) {
+        return Err(ViewError::Usage(usage));
+    }
+    Ok(())
+}
+
+/// Create a [`BufferView`] binding `range` bytes of `buffer` (starting at
+/// `offset`) to `format`.
+///
+/// `usage` must include `UNIFORM_TEXEL` or `STORAGE_TEXEL`, `format` must be
+/// supported by the device for texel buffers, and `offset` must respect
+/// `Limits::min_texel_buffer_offset_alignment`.
+pub fn create_buffer_view<'a, B: Backend>(
+    device: &mut B::Device,
+    buffer: &'a B::Buffer,
+    usage: Usage,
+    format: Format,
+    offset: u64,
+    range: Option<u64>,
+) -> Result<BufferView<'a, B>, ViewError> {
+    use device::Device;
+
+    validate_buffer_view_usage(usage)?;
+
+    if !device.format_supports_texel_buffer(format) {
+        return Err(ViewError::Unsupported);
+    }
+
+    let limits = device.get_limits();
+    if offset % limits.min_texel_buffer_offset_alignment as u64 != 0 {
+        return Err(ViewError::Alignment(offset));
+    }
+
+    Ok(BufferView { buffer, format, offset, range })
+}
+
+#[cfg(test)]
+mod create_buffer_view_tests {
+    use super::*;
+
+    #[test]
+    fn rejects_usage_without_texel_buffer_bits() {
+        assert_eq!(
+            validate_buffer_view_usage(Usage::VERTEX),
+            Err(ViewError::Usage(Usage::VERTEX))
+        );
+    }
+
+    #[test]
+    fn accepts_uniform_texel_usage() {
+        assert_eq!(validate_buffer_view_usage(Usage::UNIFORM_TEXEL), Ok(()));
+    }
+
+    #[test]
+    fn accepts_storage_texel_usage() {
+        assert_eq!(validate_buffer_view_usage(Usage::STORAGE_TEXEL), Ok(()));
+    }
+
+    // TODO: the format-support and offset-alignment branches of
+    // create_buffer_view touch B::Device/B::Buffer and need a mock backend
+    // to exercise directly; this crate doesn't define one to build against.
+}
+
+/// Tracks which byte ranges of a buffer are known to hold initialized data.
+///
+/// Reading device memory that was never written is undefined behavior on
+/// several backends, but eagerly clearing a whole buffer on creation is
+/// wasteful. Instead, a tracker is kept per buffer; before a region is
+/// first *read* (bound as an index/vertex/uniform buffer, copied from,
+/// etc.), [`drain`](InitTracker::drain) reports the sub-ranges still
+/// missing a write so the caller can emit a zero-filling transfer into
+/// just those gaps before marking them initialized.
+#[derive(Debug, Default)]
+pub struct InitTracker {
+    /// Sorted, non-overlapping, coalesced ranges known to be initialized.
+    initialized: Vec<Range<u64>>,
+}
+
+impl InitTracker {
+    /// Create a tracker with nothing marked as initialized.
+    pub fn new() -> Self {
+        InitTracker { initialized: Vec::new() }
+    }
+
+    /// Mark `range` as initialized, merging it with any ranges it
+    /// overlaps or touches so the list stays coalesced.
+    pub fn insert(&mut self, range: Range<u64>) {
+        if range.start >= range.end {
+            return;
+        }
+
+        let mut merged = range.clone();
+        let mut i = 0;
+        while i < self.initialized.len() {
+            let overlaps = self.initialized[i].start <= merged.end
+                && self.initialized[i].end >= merged.start;
+            if overlaps {
+                merged.start = merged.start.min(self.initialized[i].start);
+                merged.end = merged.end.max(self.initialized[i].end);
+                self.initialized.remove(i);
+            } else {
+                i += 1;
+            }
+        }
+
+        let pos = self.initialized.iter()
+            .position(|r| r.start > merged.start)
+            .unwrap_or(self.initialized.len());
+        self.initialized.insert(pos, merged);
+    }
+
+    /// Report the sub-ranges of `range` that are not yet initialized, then
+    /// mark the whole of `range` as initialized.
+    ///
+    /// The caller is expected to have filled (or is about to fill) exactly
+    /// the returned gaps, which is why this both reads and updates the
+    /// tracker in one step.
+    pub fn drain(&mut self, range: Range<u64>) -> Vec<Range<u64>> {
+        let mut gaps = Vec::new();
+        let mut cursor = range.start;
+
+        for r in &self.initialized {
+            if r.start >= range.end {
+                break;
+            }
+            let overlap_start = r.start.max(range.start);
+            let overlap_end = r.end.min(range.end);
+            if overlap_start > cursor {
+                gaps.push(cursor..overlap_start);
+            }
+            cursor = cursor.max(overlap_end);
+        }
+        if cursor < range.end {
+            gaps.push(cursor..range.end);
+        }
+
+        self.insert(range);
+        gaps
+    }
+
+    /// Forget all initialization state, e.g. when the buffer's backing
+    /// memory has been reused for a different resource.
+    pub fn clear(&mut self) {
+        self.initialized.clear();
+    }
+}
+
+#[cfg(test)]
+mod init_tracker_tests {
+    use super::*;
+
+    #[test]
+    fn drain_reports_the_whole_range_when_nothing_initialized() {
+        let mut tracker = InitTracker::new();
+        assert_eq!(tracker.drain(0..10), vec![0..10]);
+    }
+
+    #[test]
+    fn drain_reports_no_gaps_once_fully_initialized() {
+        let mut tracker = InitTracker::new();
+        tracker.insert(0..10);
+        assert_eq!(tracker.drain(0..10), Vec::<Range<u64>>::new());
+    }
+
+    #[test]
+    fn drain_reports_only_the_uninitialized_sub_ranges() {
+        let mut tracker = InitTracker::new();
+        tracker.insert(2..4);
+        assert_eq!(tracker.drain(0..6), vec![0..2, 4..6]);
+    }
+
+    #[test]
+    fn insert_ignores_empty_ranges() {
+        let mut tracker = InitTracker::new();
+        tracker.insert(5..5);
+        assert!(tracker.initialized.is_empty());
+    }
+
+    #[test]
+    fn insert_coalesces_touching_ranges() {
+        let mut tracker = InitTracker::new();
+        tracker.insert(0..2);
+        tracker.insert(2..4);
+        assert_eq!(tracker.initialized, vec![0..4]);
+    }
+
+    #[test]
+    fn insert_coalesces_overlapping_ranges() {
+        let mut tracker = InitTracker::new();
+        tracker.insert(0..3);
+        tracker.insert(2..5);
+        assert_eq!(tracker.initialized, vec![0..5]);
+    }
+
+    #[test]
+    fn clear_forgets_everything() {
+        let mut tracker = InitTracker::new();
+        tracker.insert(0..10);
+        tracker.clear();
+        assert_eq!(tracker.drain(0..10), vec![0..10]);
+    }
+}
+
+/// A pending barrier produced by [`RangeTracker::access`], describing the
+/// transition needed over `range` before the new access can proceed.
+#[derive(Clone, Debug, PartialEq)]
+pub struct BarrierDesc {
+    /// The sub-range of the buffer this barrier covers.
+    pub range: Range<u64>,
+    /// Source (`start`) and destination (`end`) access state.
+    pub states: Range<State>,
+    /// Source (`start`) and destination (`end`) pipeline stage.
+    pub stages: Range<PipelineStage>,
+    /// Source and destination queue family, if this barrier also performs
+    /// a queue ownership transfer.
+    pub queue_family_transfer: Option<Range<QueueFamilyId>>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+struct RangeState {
+    range: Range<u64>,
+    access: AccessType,
+    queue_family: QueueFamilyId,
+}
+
+/// Tracks the current synchronization state over sub-ranges of a single
+/// buffer, so barriers can be derived automatically from a sequence of
+/// recorded accesses instead of assembled by hand at each call site.
+///
+/// The buffer's range is kept as a non-overlapping list of
+/// `(AccessType, queue family)` entries; each recorded access splits and
+/// merges the entries it overlaps, using [`buffer_barrier`] to derive the
+/// stage/access masks for any entry that needs a barrier.
+#[derive(Debug, Default)]
+pub struct RangeTracker {
+    states: Vec<RangeState>,
+}
+
+impl RangeTracker {
+    /// Create an empty tracker; the whole buffer starts out untracked.
+    pub fn new() -> Self {
+        RangeTracker { states: Vec::new() }
+    }
+
+    /// Record an access to `range` as `new_access` on `queue_family`,
+    /// returning the barriers required to transition from whatever was
+    /// previously tracked over that range.
+    ///
+    /// A barrier is emitted wherever the previous or the new access is a
+    /// write (write-after-write, read-after-write or write-after-read), or the queue family
+    /// changed (an ownership transfer always needs an explicit release/
+    /// acquire pair, regardless of read/write). Read-after-read on the same
+    /// queue family needs no synchronization and is folded in silently.
+    pub fn access(
+        &mut self,
+        range: Range<u64>,
+        new_access: AccessType,
+        queue_family: QueueFamilyId,
+    ) -> SmallVec<[BarrierDesc; 4]> {
+        let mut barriers = SmallVec::new();
+        let mut remaining = Vec::with_capacity(self.states.len() + 1);
+
+        for existing in self.states.drain(..) {
+            if existing.range.end <= range.start || existing.range.start >= range.end {
+                remaining.push(existing);
+                continue;
+            }
+
+            if existing.range.start < range.start {
+                remaining.push(RangeState { range: existing.range.start..range.start, ..existing.clone() });
+            }
+            if existing.range.end > range.end {
+                remaining.push(RangeState { range: range.end..existing.range.end, ..existing.clone() });
+            }
+
+            let queue_family_transfer = if existing.queue_family != queue_family {
+                Some(existing.queue_family..queue_family)
+            } else {
+                None
+            };
+
+            if existing.access.is_write() || new_access.is_write() || queue_family_transfer.is_some() {
+                let (src_stage, dst_stage, src_state, dst_state) =
+                    buffer_barrier(&[existing.access], &[new_access]);
+                barriers.push(BarrierDesc {
+                    range: existing.range.start.max(range.start)..existing.range.end.min(range.end),
+                    states: src_state..dst_state,
+                    stages: src_stage..dst_stage,
+                    queue_family_transfer,
+                });
+            }
+        }
+
+        remaining.push(RangeState { range: range.clone(), access: new_access, queue_family });
+        remaining.sort_by_key(|s| s.range.start);
+
+        let mut coalesced: Vec<RangeState> = Vec::with_capacity(remaining.len());
+        for state in remaining {
+            let merge = match coalesced.last() {
+                Some(last) =>
+                    last.range.end == state.range.start
+                        && last.access == state.access
+                        && last.queue_family == state.queue_family,
+                None => false,
+            };
+            if merge {
+                coalesced.last_mut().unwrap().range.end = state.range.end;
+            } else {
+                coalesced.push(state);
+            }
+        }
+        self.states = coalesced;
+
+        barriers
+    }
+
+    /// Forget all tracked state, e.g. after a queue ownership transfer that
+    /// invalidates every barrier derived so far.
+    pub fn reset(&mut self) {
+        self.states.clear();
+    }
+}
+
+#[cfg(test)]
+mod range_tracker_tests {
+    use super::*;
+
+    #[test]
+    fn first_access_emits_no_barrier() {
+        let mut tracker = RangeTracker::new();
+        let barriers = tracker.access(0..16, AccessType::VertexBuffer, QueueFamilyId(0));
+        assert!(barriers.is_empty());
+    }
+
+    #[test]
+    fn read_after_read_same_queue_emits_no_barrier() {
+        let mut tracker = RangeTracker::new();
+        let qf = QueueFamilyId(0);
+        tracker.access(0..16, AccessType::VertexBuffer, qf);
+        let barriers = tracker.access(0..16, AccessType::IndexBuffer, qf);
+        assert!(barriers.is_empty());
+    }
+
+    #[test]
+    fn write_after_read_emits_a_barrier() {
+        let mut tracker = RangeTracker::new();
+        let qf = QueueFamilyId(0);
+        tracker.access(0..16, AccessType::VertexBuffer, qf);
+        let barriers = tracker.access(0..16, AccessType::TransferWrite, qf);
+        assert_eq!(barriers.len(), 1);
+        assert_eq!(barriers[0].queue_family_transfer, None);
+    }
+
+    #[test]
+    fn read_after_read_different_queue_family_emits_a_transfer_barrier() {
+        let mut tracker = RangeTracker::new();
+        tracker.access(0..16, AccessType::VertexBuffer, QueueFamilyId(0));
+        let barriers = tracker.access(0..16, AccessType::VertexBuffer, QueueFamilyId(1));
+        assert_eq!(barriers.len(), 1);
+        assert_eq!(
+            barriers[0].queue_family_transfer,
+            Some(QueueFamilyId(0)..QueueFamilyId(1))
+        );
+    }
+
+    #[test]
+    fn partial_overlap_splits_the_existing_range() {
+        let mut tracker = RangeTracker::new();
+        let qf = QueueFamilyId(0);
+        tracker.access(0..16, AccessType::TransferWrite, qf);
+        tracker.access(4..8, AccessType::VertexBuffer, qf);
+        assert_eq!(tracker.states.len(), 3);
+    }
+
+    #[test]
+    fn adjacent_same_access_ranges_coalesce() {
+        let mut tracker = RangeTracker::new();
+        let qf = QueueFamilyId(0);
+        tracker.access(0..8, AccessType::VertexBuffer, qf);
+        tracker.access(8..16, AccessType::VertexBuffer, qf);
+        assert_eq!(tracker.states.len(), 1);
+        assert_eq!(tracker.states[0].range, 0..16);
+    }
+
+    #[test]
+    fn reset_forgets_everything() {
+        let mut tracker = RangeTracker::new();
+        let qf = QueueFamilyId(0);
+        tracker.access(0..16, AccessType::TransferWrite, qf);
+        tracker.reset();
+        let barriers = tracker.access(0..16, AccessType::TransferWrite, qf);
+        assert!(barriers.is_empty());
+    }
+}